@@ -1,15 +1,22 @@
 #![doc = include_str!("../README.md")]
 
-use anyhow::{ensure, Context, Result};
-use clap::{crate_description, crate_name, crate_version, Parser, ValueHint};
+use anyhow::{bail, ensure, Context, Result};
+use clap::{crate_description, crate_name, crate_version, Parser, ValueEnum, ValueHint};
 use core::fmt;
 use std::error::Error;
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::{env, fs};
 use subparse::timetypes::{TimeDelta, TimePoint, TimeSpan};
-use subparse::{SrtFile, SubtitleEntry, SubtitleFileInterface};
+use subparse::{
+    get_subtitle_format, parse_bytes, MicroDVDFile, SsaFile, SrtFile, SubtitleEntry,
+    SubtitleFileInterface, SubtitleFormat, WebVttFile,
+};
+
+/// Frame rate assumed for frame-based formats (currently only MicroDVD) when no
+/// better information is available.
+const DEFAULT_FPS: f64 = 25.0;
 
 #[derive(Debug)]
 struct SubtitleError(subparse::errors::Error);
@@ -27,17 +34,63 @@ impl Error for SubtitleError {
     }
 }
 
-fn parse_range<T, U>(s: &str) -> Result<(T, U), Box<dyn Error + Send + Sync + 'static>>
-where
-    T: std::str::FromStr,
-    T::Err: Error + Send + Sync + 'static,
-    U: std::str::FromStr,
-    U::Err: Error + Send + Sync + 'static,
-{
+/// Subtitle format, as selectable on the command line. Mirrors
+/// `subparse::SubtitleFormat`, which doesn't implement `ValueEnum` itself.
+///
+/// VobSub is deliberately not offered here: its timing lives in a companion
+/// `.idx` text file, not in the binary `.sub` SPU stream, so reading just the
+/// `.sub` bytes through `parse_bytes` can't produce correct timestamps.
+/// Support for the idx+sub pair can be added once it's actually wired up.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Srt,
+    Ssa,
+    MicroDvd,
+    WebVtt,
+}
+
+impl From<Format> for SubtitleFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Srt => SubtitleFormat::SubRip,
+            Format::Ssa => SubtitleFormat::SubStationAlpha,
+            Format::MicroDvd => SubtitleFormat::MicroDVD,
+            Format::WebVtt => SubtitleFormat::WebVTT,
+        }
+    }
+}
+
+/// Parses a block boundary as either bare seconds (`90.5`) or a subtitle
+/// timecode — `HH:MM:SS`, `MM:SS`, or `:SS`, with `,` or `.` as the
+/// fractional separator, as seen in real `.srt` cue lines. The form is
+/// detected by the presence of `:`, matching srtune's convention.
+fn parse_time(s: &str) -> Result<f64, Box<dyn Error + Send + Sync + 'static>> {
+    if !s.contains(':') {
+        return Ok(s.parse()?);
+    }
+    let mut seconds = 0.0;
+    for part in s.replace(',', ".").split(':') {
+        let component: f64 = if part.is_empty() { 0.0 } else { part.parse()? };
+        seconds = seconds * 60.0 + component;
+    }
+    Ok(seconds)
+}
+
+fn parse_range(s: &str) -> Result<(f64, f64), Box<dyn Error + Send + Sync + 'static>> {
     let pos = s
         .find('-')
         .ok_or_else(|| format!("invalid KEY=value: no `-` found in `{}`", s))?;
-    Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
+    Ok((parse_time(&s[..pos])?, parse_time(&s[pos + 1..])?))
+}
+
+/// Parses a `--scale` value, either a bare ratio (`0.959`) or an `a/b`
+/// fraction (`24000/25025`), matching srtune's convention for the same
+/// option.
+fn parse_scale(s: &str) -> Result<f64, Box<dyn Error + Send + Sync + 'static>> {
+    match s.split_once('/') {
+        Some((numerator, denominator)) => Ok(numerator.parse::<f64>()? / denominator.parse::<f64>()?),
+        None => Ok(s.parse()?),
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -51,25 +104,81 @@ pub struct Args {
     #[arg(short = 'i', long, value_hint = ValueHint::FilePath)]
     pub input: Option<PathBuf>,
 
-    /// Blocks, from start to finish.
-    #[arg(required=true, value_parser=parse_range::<f64, f64>)]
+    /// Input subtitle format; detected from the input file's extension and
+    /// contents if not specified.
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// Output subtitle format; same as the input format if not specified.
+    #[arg(long, value_enum)]
+    pub output_format: Option<Format>,
+
+    /// Align the input to `REFERENCE`'s timing by cross-correlating subtitle
+    /// activity and shifting the input by the best-matching offset. Applied
+    /// before `--shift`/`--scale`, which still run afterward for any further
+    /// manual correction.
+    #[arg(long, value_name = "REFERENCE", value_hint = ValueHint::FilePath)]
+    pub sync_to: Option<PathBuf>,
+
+    /// Shift every timestamp by this many seconds (negative to shift
+    /// earlier). Combined with `--scale` into a single `scale * t + shift`
+    /// transform, applied once before block trimming.
+    #[arg(long, allow_hyphen_values = true)]
+    pub shift: Option<f64>,
+
+    /// Scale every timestamp by this ratio, as a bare number (`0.959`) or an
+    /// `a/b` fraction (`24000/25025`). Combined with `--shift` into a single
+    /// `scale * t + shift` transform, applied once before block trimming;
+    /// useful for fixing subtitles authored for one framerate and played
+    /// back at another, e.g. 25 fps on a 23.976 fps release
+    /// (`--scale 24000/25025`).
+    #[arg(long, value_parser = parse_scale)]
+    pub scale: Option<f64>,
+
+    /// Shift each block on its own so that its start lands at 0:00, instead
+    /// of splicing blocks back-to-back in the output; the blocks' outputs
+    /// therefore overlap. This is subtrim's original behavior, kept for
+    /// backward compatibility. Ignored with
+    /// `--cut`, which always splices around the removed ranges.
+    #[arg(long)]
+    pub absolute: bool,
+
+    /// Treat `blocks` as ranges to remove instead of ranges to keep: the
+    /// given ranges are cut out and the surrounding material is spliced
+    /// together, closing the gap.
+    #[arg(long, alias = "remove")]
+    pub cut: bool,
+
+    /// Blocks, from start to finish. Ranges to keep, unless `--cut` is given,
+    /// in which case ranges to remove. Each boundary accepts either bare
+    /// seconds (`90.5`) or a timecode (`00:01:30,500`).
+    #[arg(required=true, value_parser=parse_range)]
     pub blocks: Vec<(f64, f64)>,
 }
 
-fn trim_subtitles(
+fn seconds_to_timepoint(seconds: f64) -> TimePoint {
+    TimePoint::from_components(0, 0, seconds.trunc() as i64, (seconds.fract() * 1000.0) as i64)
+}
+
+fn seconds_to_timedelta(seconds: f64) -> TimeDelta {
+    TimeDelta::from_components(0, 0, seconds.trunc() as i64, (seconds.fract() * 1000.0) as i64)
+}
+
+/// Shifts every entry back by `start`, so that `start` lands at absolute zero,
+/// and keeps what overlaps `[0, finish]` on that shifted timeline, clamped to
+/// it. `finish` is thus measured from the block's start, not from 0:00 of the
+/// input: the entries taken are those overlapping `[start, start + finish]`
+/// of the input. This reproduces subtrim's original behavior, where every
+/// block's output begins near 0:00 regardless of where any other block
+/// landed; kept around for `--absolute`.
+fn trim_subtitles_absolute(
     subtitles: &Vec<SubtitleEntry>,
     start: f64,
-    duration: f64,
-    new_subtitles: &mut Vec<(TimeSpan, std::string::String)>,
+    finish: f64,
+    new_subtitles: &mut Vec<SubtitleEntry>,
 ) {
-    let start_delta =
-        TimeDelta::from_components(0, 0, start.trunc() as i64, (start.fract() * 1000.0) as i64);
-    let end_point = TimePoint::from_components(
-        0,
-        0,
-        duration.trunc() as i64,
-        (duration.fract() * 1000.0) as i64,
-    );
+    let start_delta = seconds_to_timedelta(start);
+    let end_point = seconds_to_timepoint(finish);
     new_subtitles.extend(subtitles.into_iter().filter_map(|entry| {
         let mut new_timespan = entry.timespan - start_delta;
         if new_timespan.end.is_negative() || new_timespan.start >= end_point {
@@ -81,11 +190,213 @@ fn trim_subtitles(
         if new_timespan.end > end_point {
             new_timespan = TimeSpan::new(new_timespan.start, end_point);
         }
-        let line = entry.line.clone().unwrap_or_else(|| String::new());
-        Some((new_timespan, line))
+        Some(SubtitleEntry {
+            timespan: new_timespan,
+            line: entry.line.clone(),
+        })
     }));
 }
 
+/// Extracts the entries overlapping `[start, finish]`, clamped to that range,
+/// and splices them onto the end of the output so far: `*cursor` tracks how
+/// much output has already been emitted, and is advanced by `finish - start`
+/// once this block is appended. Unlike `trim_subtitles_absolute`, consecutive
+/// blocks are joined back-to-back instead of each restarting near 0:00.
+fn trim_subtitles_gapless(
+    subtitles: &Vec<SubtitleEntry>,
+    start: f64,
+    finish: f64,
+    cursor: &mut TimeDelta,
+    new_subtitles: &mut Vec<SubtitleEntry>,
+) {
+    let start_point = seconds_to_timepoint(start);
+    let finish_point = seconds_to_timepoint(finish);
+    let start_delta = seconds_to_timedelta(start);
+    let finish_delta = seconds_to_timedelta(finish);
+    // Shift each entry from the input timeline to `start..finish` at the
+    // origin, then onto the output timeline at `*cursor`.
+    let shift = start_delta - *cursor;
+    new_subtitles.extend(subtitles.into_iter().filter_map(|entry| {
+        if entry.timespan.end.is_negative() || entry.timespan.end <= start_point {
+            return None;
+        }
+        if entry.timespan.start >= finish_point {
+            return None;
+        }
+        let mut clamped = entry.timespan;
+        if clamped.start < start_point {
+            clamped = TimeSpan::new(start_point, clamped.end);
+        }
+        if clamped.end > finish_point {
+            clamped = TimeSpan::new(clamped.start, finish_point);
+        }
+        Some(SubtitleEntry {
+            timespan: clamped - shift,
+            line: entry.line.clone(),
+        })
+    }));
+    *cursor += finish_delta - start_delta;
+}
+
+/// Bin size used to rasterize subtitle activity for `--sync-to`.
+const SYNC_BIN_MSECS: i64 = 50;
+
+/// Largest offset, in either direction, that `--sync-to` will consider.
+const SYNC_MAX_LAG_SECS: f64 = 60.0;
+
+/// Largest start-to-finish span that `--sync-to` will rasterize. Guards
+/// against a corrupt or wildly out-of-range timestamp (e.g. a stray
+/// `9999:59:59`-style cue) turning a single bogus entry into a
+/// multi-gigabyte allocation and an O(bin_count * lag) comparison to match.
+const SYNC_MAX_SPAN_SECS: f64 = 24.0 * 60.0 * 60.0;
+
+/// Rasterizes `entries` into a boolean timeline: bin `i` is `true` if any
+/// entry is active during `[origin_msecs + i * bin_msecs, origin_msecs + (i
+/// + 1) * bin_msecs)`.
+fn rasterize_activity(
+    entries: &[SubtitleEntry],
+    origin_msecs: i64,
+    bin_msecs: i64,
+    bin_count: usize,
+) -> Vec<bool> {
+    let mut bins = vec![false; bin_count];
+    for entry in entries {
+        let start = (entry.timespan.start.msecs() - origin_msecs) / bin_msecs;
+        let end = (entry.timespan.end.msecs() - origin_msecs + bin_msecs - 1) / bin_msecs;
+        let start = start.clamp(0, bin_count as i64) as usize;
+        let end = end.clamp(0, bin_count as i64) as usize;
+        for bin in &mut bins[start..end] {
+            *bin = true;
+        }
+    }
+    bins
+}
+
+/// Finds the offset, in seconds, that best aligns `input`'s subtitle
+/// activity with `reference`'s, by rasterizing both to a shared timeline and
+/// cross-correlating over a bounded window of lags. Ties are broken toward
+/// the smallest absolute shift.
+fn sync_shift_secs(reference: &[SubtitleEntry], input: &[SubtitleEntry]) -> Result<f64> {
+    ensure!(
+        !reference.is_empty(),
+        "Reference subtitle file has no entries to sync against"
+    );
+    ensure!(
+        !input.is_empty(),
+        "Input subtitle file has no entries to sync against"
+    );
+
+    let origin_msecs = reference
+        .iter()
+        .chain(input.iter())
+        .map(|entry| entry.timespan.start.msecs())
+        .min()
+        .unwrap();
+    let end_msecs = reference
+        .iter()
+        .chain(input.iter())
+        .map(|entry| entry.timespan.end.msecs())
+        .max()
+        .unwrap();
+    let span_secs = (end_msecs - origin_msecs) as f64 / 1000.0;
+    ensure!(
+        span_secs <= SYNC_MAX_SPAN_SECS,
+        "Reference/input subtitles span {span_secs:.0}s, further apart than the {SYNC_MAX_SPAN_SECS:.0}s --sync-to supports; check for a corrupt timestamp"
+    );
+    let bin_count = (((end_msecs - origin_msecs) / SYNC_BIN_MSECS) as usize) + 1;
+
+    let reference_bins = rasterize_activity(reference, origin_msecs, SYNC_BIN_MSECS, bin_count);
+    let input_bins = rasterize_activity(input, origin_msecs, SYNC_BIN_MSECS, bin_count);
+
+    let max_lag = (SYNC_MAX_LAG_SECS * 1000.0 / SYNC_BIN_MSECS as f64).round() as i64;
+    let mut best_lag = 0i64;
+    let mut best_score = -1i64;
+    for lag in -max_lag..=max_lag {
+        let mut score = 0i64;
+        for i in 0..bin_count {
+            let j = i as i64 - lag;
+            if j < 0 || j as usize >= bin_count {
+                continue;
+            }
+            if reference_bins[i] && input_bins[j as usize] {
+                score += 1;
+            }
+        }
+        if score > best_score || (score == best_score && lag.abs() < best_lag.abs()) {
+            best_score = score;
+            best_lag = lag;
+        }
+    }
+
+    Ok(best_lag as f64 * SYNC_BIN_MSECS as f64 / 1000.0)
+}
+
+/// Applies the affine transform `t' = scale * t + shift` to every
+/// timestamp, mirroring the move/scale operations in srtune. Operates in
+/// whole milliseconds since `scale` is typically an irrational-looking
+/// framerate ratio that doesn't divide evenly into `TimeDelta`'s
+/// hour/minute/second/millisecond components.
+fn rescale_subtitles(subtitles: &mut [SubtitleEntry], scale: f64, shift: f64) {
+    let shift_msecs = (shift * 1000.0).round() as i64;
+    let transform = |point: TimePoint| {
+        TimePoint::from_msecs((point.msecs() as f64 * scale).round() as i64 + shift_msecs)
+    };
+    for entry in subtitles.iter_mut() {
+        entry.timespan = TimeSpan::new(
+            transform(entry.timespan.start),
+            transform(entry.timespan.end),
+        );
+    }
+}
+
+/// Removes every cut range from `subtitles` and splices the surrounding
+/// material together, closing each gap: entries fully inside a cut are
+/// dropped, entries straddling a cut boundary are clipped to it, and every
+/// later timestamp is pulled back by the accumulated removed duration. The
+/// dual of `trim_subtitles_gapless`; `cuts` must be ordered and disjoint, as
+/// enforced by `validate_blocks`.
+fn cut_subtitles(subtitles: &Vec<SubtitleEntry>, cuts: &[(f64, f64)]) -> Vec<SubtitleEntry> {
+    let cuts: Vec<(TimePoint, TimePoint, TimeDelta)> = cuts
+        .iter()
+        .map(|&(start, finish)| {
+            (
+                seconds_to_timepoint(start),
+                seconds_to_timepoint(finish),
+                seconds_to_timedelta(finish - start),
+            )
+        })
+        .collect();
+
+    let map_point = |point: TimePoint| -> TimePoint {
+        let mut offset = TimeDelta::from_components(0, 0, 0, 0);
+        for &(c0, c1, len) in &cuts {
+            if point <= c0 {
+                break;
+            } else if point >= c1 {
+                offset += len;
+            } else {
+                return c0 - offset;
+            }
+        }
+        point - offset
+    };
+
+    subtitles
+        .into_iter()
+        .filter_map(|entry| {
+            let start = map_point(entry.timespan.start);
+            let end = map_point(entry.timespan.end);
+            if start >= end {
+                return None;
+            }
+            Some(SubtitleEntry {
+                timespan: TimeSpan::new(start, end),
+                line: entry.line.clone(),
+            })
+        })
+        .collect()
+}
+
 fn validate_blocks(blocks: &[(f64, f64)]) -> Result<()> {
     let mut time = 0.0;
     for block in blocks {
@@ -96,6 +407,63 @@ fn validate_blocks(blocks: &[(f64, f64)]) -> Result<()> {
     Ok(())
 }
 
+/// Determines which subtitle format to parse `data` as: `format` if given,
+/// otherwise auto-detected from `filename_hint`'s extension and the file's
+/// contents.
+fn detect_format(
+    format: Option<Format>,
+    filename_hint: Option<&Path>,
+    data: &[u8],
+) -> Result<SubtitleFormat> {
+    let format = match format {
+        Some(format) => format.into(),
+        None => {
+            let extension = filename_hint.and_then(|path| path.extension());
+            get_subtitle_format(extension, data)
+                .map_err(SubtitleError)
+                .context("Could not detect subtitle format")?
+        }
+    };
+    // VobSub's timing lives in a companion `.idx` file, not in the `.sub`
+    // bytes alone; we don't read the pair, so reject it rather than produce
+    // bogus timestamps. See the `Format` doc comment for details.
+    ensure!(
+        !matches!(
+            format,
+            SubtitleFormat::VobSubIdx | SubtitleFormat::VobSubSub
+        ),
+        "VobSub subtitles are not supported (timing lives in the companion `.idx` file, which subtrim does not read)"
+    );
+    Ok(format)
+}
+
+/// Builds a fresh subtitle file of `format` out of `entries`, discarding any
+/// format-specific metadata (styles, positioning, etc.) the input may have
+/// carried.
+fn create_subtitle_file(
+    format: SubtitleFormat,
+    entries: Vec<(TimeSpan, String)>,
+) -> Result<Box<dyn SubtitleFileInterface>> {
+    Ok(match format {
+        SubtitleFormat::SubRip => {
+            Box::new(SrtFile::create(entries).map_err(SubtitleError)?)
+        }
+        SubtitleFormat::SubStationAlpha => {
+            Box::new(SsaFile::create(entries).map_err(SubtitleError)?)
+        }
+        SubtitleFormat::MicroDVD => {
+            Box::new(MicroDVDFile::create(entries, DEFAULT_FPS).map_err(SubtitleError)?)
+        }
+        SubtitleFormat::WebVTT => {
+            Box::new(WebVttFile::create(entries).map_err(SubtitleError)?)
+        }
+        _ => bail!(
+            "Writing subtitles in the `{:?}` format is not supported",
+            format
+        ),
+    })
+}
+
 fn try_main() -> Result<()> {
     println!(
         "{}",
@@ -110,39 +478,86 @@ fn try_main() -> Result<()> {
     // Validate blocks are in sequence and disjoint.
     validate_blocks(&options.blocks)?;
 
-    let input_string = match options.input {
-        Some(input) => fs::read_to_string(&input).with_context(|| {
+    let input_data = match &options.input {
+        Some(input) => fs::read(input).with_context(|| {
             format!(
                 "Could not read subtitles from file `{}'",
                 input.to_string_lossy()
             )
         })?,
         None => {
-            let mut result = String::new();
+            let mut result = Vec::new();
             io::stdin()
-                .read_to_string(&mut result)
+                .read_to_end(&mut result)
                 .context("Could not read subtitles from stdin")?;
             result
         }
     };
 
-    let subtitles = SrtFile::parse(&input_string)
-        .map_err(|e| SubtitleError(e))
-        .context("Could not parse input file as SRT")?
+    let input_format = detect_format(options.format, options.input.as_deref(), &input_data)?;
+    let input_file = parse_bytes(input_format, &input_data, DEFAULT_FPS)
+        .map_err(SubtitleError)
+        .context("Could not parse input file")?;
+
+    let mut subtitles = input_file
         .get_subtitle_entries()
-        .map_err(|e| SubtitleError(e))
+        .map_err(SubtitleError)
         .context("Could not retrieve subtitle entries")?;
 
-    let mut new_subtitles = Vec::new();
-    for block in options.blocks {
-        trim_subtitles(&subtitles, block.0, block.1, &mut new_subtitles);
+    if let Some(reference_path) = &options.sync_to {
+        let reference_data = fs::read(reference_path).with_context(|| {
+            format!(
+                "Could not read reference subtitles from file `{}'",
+                reference_path.to_string_lossy()
+            )
+        })?;
+        let reference_format = detect_format(None, Some(reference_path), &reference_data)?;
+        let reference_subtitles = parse_bytes(reference_format, &reference_data, DEFAULT_FPS)
+            .map_err(SubtitleError)
+            .context("Could not parse reference subtitle file")?
+            .get_subtitle_entries()
+            .map_err(SubtitleError)
+            .context("Could not retrieve reference subtitle entries")?;
+        let shift = sync_shift_secs(&reference_subtitles, &subtitles)?;
+        rescale_subtitles(&mut subtitles, 1.0, shift);
     }
 
-    let subtitle_data = SrtFile::create(new_subtitles)
-        .map_err(|e| SubtitleError(e))
-        .context("Could not create subtitles from data")?
+    if options.shift.is_some() || options.scale.is_some() {
+        rescale_subtitles(
+            &mut subtitles,
+            options.scale.unwrap_or(1.0),
+            options.shift.unwrap_or(0.0),
+        );
+    }
+
+    let new_subtitles = if options.cut {
+        cut_subtitles(&subtitles, &options.blocks)
+    } else {
+        let mut new_subtitles = Vec::new();
+        if options.absolute {
+            for block in &options.blocks {
+                trim_subtitles_absolute(&subtitles, block.0, block.1, &mut new_subtitles);
+            }
+        } else {
+            let mut cursor = TimeDelta::from_components(0, 0, 0, 0);
+            for block in &options.blocks {
+                trim_subtitles_gapless(&subtitles, block.0, block.1, &mut cursor, &mut new_subtitles);
+            }
+        }
+        new_subtitles
+    };
+
+    let output_format = match options.output_format {
+        Some(format) => format.into(),
+        None => input_format,
+    };
+    let new_subtitles = new_subtitles
+        .into_iter()
+        .map(|entry| (entry.timespan, entry.line.unwrap_or_default()))
+        .collect();
+    let subtitle_data = create_subtitle_file(output_format, new_subtitles)?
         .to_data()
-        .map_err(|e| SubtitleError(e))
+        .map_err(SubtitleError)
         .context("Could not create subtitle data")?;
 
     match options.output {
@@ -172,3 +587,158 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start: f64, end: f64, line: &str) -> SubtitleEntry {
+        SubtitleEntry {
+            timespan: TimeSpan::new(seconds_to_timepoint(start), seconds_to_timepoint(end)),
+            line: Some(line.to_string()),
+        }
+    }
+
+    fn secs(point: TimePoint) -> f64 {
+        point.msecs() as f64 / 1000.0
+    }
+
+    #[test]
+    fn cut_clips_entry_straddling_start_boundary() {
+        let subtitles = vec![entry(8.0, 12.0, "a")];
+        let result = cut_subtitles(&subtitles, &[(10.0, 20.0)]);
+        assert_eq!(result.len(), 1);
+        assert_eq!(secs(result[0].timespan.start), 8.0);
+        assert_eq!(secs(result[0].timespan.end), 10.0);
+    }
+
+    #[test]
+    fn cut_clips_entry_straddling_end_boundary() {
+        let subtitles = vec![entry(18.0, 25.0, "a")];
+        let result = cut_subtitles(&subtitles, &[(10.0, 20.0)]);
+        assert_eq!(result.len(), 1);
+        // The 10s cut closes, so the surviving [20, 25) slides back to [10, 15).
+        assert_eq!(secs(result[0].timespan.start), 10.0);
+        assert_eq!(secs(result[0].timespan.end), 15.0);
+    }
+
+    #[test]
+    fn cut_drops_entry_fully_inside_range() {
+        let subtitles = vec![entry(12.0, 15.0, "a")];
+        let result = cut_subtitles(&subtitles, &[(10.0, 20.0)]);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn cut_accumulates_offset_across_multiple_cuts() {
+        let subtitles = vec![
+            entry(0.0, 5.0, "a"),
+            entry(25.0, 30.0, "b"),
+            entry(45.0, 50.0, "c"),
+        ];
+        let result = cut_subtitles(&subtitles, &[(10.0, 20.0), (35.0, 40.0)]);
+        assert_eq!(result.len(), 3);
+        assert_eq!(secs(result[0].timespan.start), 0.0);
+        assert_eq!(secs(result[0].timespan.end), 5.0);
+        // After the first (10s) cut closes: 25 - 10 = 15, 30 - 10 = 20.
+        assert_eq!(secs(result[1].timespan.start), 15.0);
+        assert_eq!(secs(result[1].timespan.end), 20.0);
+        // After both cuts (10s + 5s) close: 45 - 15 = 30, 50 - 15 = 35.
+        assert_eq!(secs(result[2].timespan.start), 30.0);
+        assert_eq!(secs(result[2].timespan.end), 35.0);
+    }
+
+    #[test]
+    fn sync_shift_secs_finds_known_lag() {
+        let reference = vec![entry(10.0, 12.0, "a"), entry(20.0, 22.0, "b")];
+        // Same activity, but every entry starts 3s earlier than in `reference`.
+        let input = vec![entry(7.0, 9.0, "a"), entry(17.0, 19.0, "b")];
+        let shift = sync_shift_secs(&reference, &input).unwrap();
+        assert_eq!(shift, 3.0);
+    }
+
+    #[test]
+    fn sync_shift_secs_errors_on_empty_reference() {
+        let reference: Vec<SubtitleEntry> = vec![];
+        let input = vec![entry(0.0, 1.0, "a")];
+        assert!(sync_shift_secs(&reference, &input).is_err());
+    }
+
+    #[test]
+    fn sync_shift_secs_errors_on_empty_input() {
+        let reference = vec![entry(0.0, 1.0, "a")];
+        let input: Vec<SubtitleEntry> = vec![];
+        assert!(sync_shift_secs(&reference, &input).is_err());
+    }
+
+    #[test]
+    fn sync_shift_secs_errors_on_span_beyond_sanity_bound() {
+        let reference = vec![entry(0.0, 1.0, "a")];
+        // A stray far-future timestamp should be rejected, not rasterized.
+        let input = vec![entry(0.0, 1.0, "a"), entry(999_999.0, 1_000_000.0, "b")];
+        assert!(sync_shift_secs(&reference, &input).is_err());
+    }
+
+    #[test]
+    fn parse_time_accepts_bare_seconds() {
+        assert_eq!(parse_time("90.5").unwrap(), 90.5);
+    }
+
+    #[test]
+    fn parse_time_accepts_full_timecode_with_comma() {
+        assert_eq!(parse_time("00:01:30,500").unwrap(), 90.5);
+    }
+
+    #[test]
+    fn parse_time_accepts_full_timecode_with_dot() {
+        assert_eq!(parse_time("00:02:10.000").unwrap(), 130.0);
+    }
+
+    #[test]
+    fn parse_time_accepts_minutes_and_seconds() {
+        assert_eq!(parse_time("1:30").unwrap(), 90.0);
+    }
+
+    #[test]
+    fn parse_time_accepts_leading_colon_as_bare_seconds() {
+        assert_eq!(parse_time(":30").unwrap(), 30.0);
+    }
+
+    #[test]
+    fn parse_range_mixes_timecodes_and_bare_seconds() {
+        assert_eq!(
+            parse_range("00:01:30,500-130.0").unwrap(),
+            (90.5, 130.0)
+        );
+    }
+
+    #[test]
+    fn parse_range_rejects_missing_separator() {
+        assert!(parse_range("90.5").is_err());
+    }
+
+    #[test]
+    fn rescale_subtitles_applies_shift_only() {
+        let mut subtitles = vec![entry(10.0, 12.0, "a")];
+        rescale_subtitles(&mut subtitles, 1.0, 5.0);
+        assert_eq!(secs(subtitles[0].timespan.start), 15.0);
+        assert_eq!(secs(subtitles[0].timespan.end), 17.0);
+    }
+
+    #[test]
+    fn rescale_subtitles_applies_scale_only() {
+        let mut subtitles = vec![entry(10.0, 20.0, "a")];
+        rescale_subtitles(&mut subtitles, 2.0, 0.0);
+        assert_eq!(secs(subtitles[0].timespan.start), 20.0);
+        assert_eq!(secs(subtitles[0].timespan.end), 40.0);
+    }
+
+    #[test]
+    fn rescale_subtitles_composes_scale_then_shift() {
+        let mut subtitles = vec![entry(10.0, 20.0, "a")];
+        // t' = scale * t + shift
+        rescale_subtitles(&mut subtitles, 2.0, 5.0);
+        assert_eq!(secs(subtitles[0].timespan.start), 25.0);
+        assert_eq!(secs(subtitles[0].timespan.end), 45.0);
+    }
+}